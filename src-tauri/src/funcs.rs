@@ -0,0 +1,4 @@
+pub mod archive;
+pub mod copy;
+pub mod error;
+pub mod files;
@@ -0,0 +1,290 @@
+//! Archive creation and extraction.
+//!
+//! Supports zip, tar.gz, tar.xz and tar.zst. For the xz and zstd backends the
+//! compression window (a.k.a. dictionary size) is tunable: a larger window
+//! finds matches across a bigger span of the input and so produces markedly
+//! smaller archives on large trees, at the cost of proportionally more memory
+//! during both compression and decompression (roughly the window size, per
+//! direction). The default of [`DEFAULT_WINDOW_LOG`] (a 64 MiB window) is a
+//! moderate setting; callers compressing large trees on a memory-rich machine
+//! can raise it to trade RAM for smaller output.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::Emitter;
+
+/// Archive container/codec selected by the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+    TarXz,
+    TarZst,
+}
+
+/// Default compression window, expressed as a base-2 log of the size in bytes.
+/// `26` == a 64 MiB window, which needs on the order of 64 MiB of RAM per
+/// direction — a reasonable default for desktop use.
+pub const DEFAULT_WINDOW_LOG: u32 = 26;
+
+/// Smallest and largest accepted compression window (base-2 log of the size in
+/// bytes). Bounded so `1 << window_log` stays within a `u32` dict size and
+/// within what zstd accepts: `10` == 1 KiB, `30` == 1 GiB. xz needs a window
+/// of at least 4 KiB (`12`) and fails to initialise below that.
+pub const MIN_WINDOW_LOG: u32 = 10;
+pub const MAX_WINDOW_LOG: u32 = 30;
+
+/// Bytes-processed/total progress for an archive operation in either direction.
+#[derive(Debug, Clone, Serialize)]
+struct ArchiveProgress {
+    processed: u64,
+    total: u64,
+}
+
+fn emit_progress(app: &tauri::AppHandle, processed: u64, total: u64) {
+    let _ = app.emit("archive-progress", ArchiveProgress { processed, total });
+}
+
+/// Recursively collect every file under `source`, pairing each absolute path
+/// with the relative path it should take inside the archive.
+fn collect_files(source: &Path, base: &Path, out: &mut Vec<(PathBuf, PathBuf)>) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(source)?;
+    if metadata.is_dir() {
+        for entry in fs::read_dir(source)? {
+            collect_files(&entry?.path(), base, out)?;
+        }
+    } else {
+        let name = source.strip_prefix(base).unwrap_or(source).to_path_buf();
+        out.push((source.to_path_buf(), name));
+    }
+    Ok(())
+}
+
+/// Gather the archive members for a set of sources, rooting each relative name
+/// at the source's own parent so `foo/bar.txt` stays `foo/bar.txt`.
+fn gather(sources: &[String]) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files = Vec::new();
+    for source in sources {
+        let path = Path::new(source);
+        let base = path.parent().unwrap_or(path);
+        collect_files(path, base, &mut files)?;
+    }
+    Ok(files)
+}
+
+/// Stream the gathered files into a `tar` archive, updating progress per file.
+fn write_tar<W: Write>(
+    app: &tauri::AppHandle,
+    writer: W,
+    files: &[(PathBuf, PathBuf)],
+    total: u64,
+) -> io::Result<()> {
+    let mut builder = tar::Builder::new(writer);
+    let mut processed = 0u64;
+    for (path, name) in files {
+        builder.append_path_with_name(path, name)?;
+        processed += fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        emit_progress(app, processed, total);
+    }
+    builder.into_inner()?.flush()?;
+    Ok(())
+}
+
+/// Create an archive at `dest` from `sources`.
+///
+/// `level` is the backend compression level; `window_log` overrides the
+/// compression window for the xz and zstd formats (see the module docs for the
+/// memory tradeoff) and is ignored by zip and gzip, which have no equivalent
+/// knob. `archive-progress` events are emitted as bytes are processed.
+#[tauri::command]
+pub async fn create_archive(
+    app: tauri::AppHandle,
+    sources: Vec<String>,
+    dest: String,
+    format: ArchiveFormat,
+    level: u32,
+    window_log: Option<u32>,
+) -> Result<(), String> {
+    let files = gather(&sources).map_err(|e| format!("Failed to read sources: {}", e))?;
+    let total: u64 = files
+        .iter()
+        .map(|(p, _)| fs::metadata(p).map(|m| m.len()).unwrap_or(0))
+        .sum();
+    let window_log = window_log.unwrap_or(DEFAULT_WINDOW_LOG);
+    // The window is used as `1 << window_log` bytes in a u32 dict size, so
+    // reject values that would overflow the shift (or exceed what the backends
+    // accept) rather than panicking on otherwise-valid caller input.
+    if matches!(format, ArchiveFormat::TarXz | ArchiveFormat::TarZst)
+        && !(MIN_WINDOW_LOG..=MAX_WINDOW_LOG).contains(&window_log)
+    {
+        return Err(format!(
+            "window_log {} out of range ({}..={})",
+            window_log, MIN_WINDOW_LOG, MAX_WINDOW_LOG
+        ));
+    }
+
+    let out = File::create(&dest).map_err(|e| format!("Failed to create {}: {}", dest, e))?;
+
+    match format {
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipWriter::new(out);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .compression_level(Some(level as i64));
+            let mut processed = 0u64;
+            let mut buf = Vec::new();
+            for (path, name) in &files {
+                zip.start_file(name.to_string_lossy(), options)
+                    .map_err(|e| format!("Failed to add {}: {}", name.display(), e))?;
+                buf.clear();
+                File::open(path)
+                    .and_then(|mut f| f.read_to_end(&mut buf))
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                zip.write_all(&buf)
+                    .map_err(|e| format!("Failed to write {}: {}", name.display(), e))?;
+                processed += buf.len() as u64;
+                emit_progress(&app, processed, total);
+            }
+            zip.finish().map_err(|e| format!("Failed to finalize zip: {}", e))?;
+        }
+        ArchiveFormat::TarGz => {
+            let encoder = flate2::write::GzEncoder::new(out, flate2::Compression::new(level));
+            write_tar(&app, encoder, &files, total).map_err(|e| e.to_string())?;
+        }
+        ArchiveFormat::TarXz => {
+            // Build an lzma2 filter at the requested preset but override its
+            // dictionary size (the xz analogue of the zstd window) so the
+            // tunable applies to xz as well.
+            let mut opts = xz2::stream::LzmaOptions::new_preset(level)
+                .map_err(|e| format!("Failed to init xz: {}", e))?;
+            opts.dict_size(1u32 << window_log);
+            let mut filters = xz2::stream::Filters::new();
+            filters.lzma2(&opts);
+            let stream = xz2::stream::Stream::new_stream_encoder(&filters, xz2::stream::Check::Crc64)
+                .map_err(|e| format!("Failed to init xz: {}", e))?;
+            let encoder = xz2::write::XzEncoder::new_stream(out, stream);
+            write_tar(&app, encoder, &files, total).map_err(|e| e.to_string())?;
+        }
+        ArchiveFormat::TarZst => {
+            let mut encoder = zstd::stream::write::Encoder::new(out, level as i32)
+                .map_err(|e| format!("Failed to init zstd: {}", e))?;
+            encoder
+                .set_parameter(zstd::zstd_safe::CParameter::WindowLog(window_log))
+                .map_err(|e| format!("Failed to set zstd window: {}", e))?;
+            write_tar(&app, encoder.auto_finish(), &files, total).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Wraps a reader to count the compressed bytes consumed, so extraction
+/// progress is reported against the same (compressed) basis as `total` and
+/// never overshoots 100%.
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Join `entry` onto `dest`, rejecting any path that would escape the
+/// destination directory (the classic zip-slip guard).
+fn safe_join(dest: &Path, entry: &Path) -> Result<PathBuf, String> {
+    let mut out = dest.to_path_buf();
+    for component in entry.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("Refusing unsafe archive entry: {}", entry.display())),
+        }
+    }
+    Ok(out)
+}
+
+/// Extract an archive at `archive` into the directory `dest`, inferring the
+/// format from the file extension. Every entry is path-checked before being
+/// written so a malicious archive cannot escape `dest`.
+#[tauri::command]
+pub async fn extract_archive(
+    app: tauri::AppHandle,
+    archive: String,
+    dest: String,
+) -> Result<(), String> {
+    let dest = PathBuf::from(&dest);
+    let compressed_total = fs::metadata(&archive).map(|m| m.len()).unwrap_or(0);
+    let lower = archive.to_lowercase();
+
+    if lower.ends_with(".zip") {
+        let file = File::open(&archive).map_err(|e| format!("Failed to open {}: {}", archive, e))?;
+        let mut zip =
+            zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip: {}", e))?;
+        // zip is random-access, so sum uncompressed entry sizes up front and
+        // report progress against that same basis as we unpack.
+        let total: u64 = (0..zip.len())
+            .filter_map(|i| zip.by_index(i).ok().map(|e| e.size()))
+            .sum();
+        let mut processed = 0u64;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+            let name = entry
+                .enclosed_name()
+                .ok_or_else(|| format!("Refusing unsafe archive entry: {}", entry.name()))?;
+            let target = safe_join(&dest, &name)?;
+            if entry.is_dir() {
+                fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+            } else {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                let mut out = File::create(&target).map_err(|e| e.to_string())?;
+                io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+            }
+            processed += entry.size();
+            emit_progress(&app, processed, total);
+        }
+    } else {
+        // tar is a forward-only stream, so we can't cheaply know the total
+        // uncompressed size up front. Count compressed bytes read from the
+        // source file instead, reporting against the archive's own size.
+        let file = File::open(&archive).map_err(|e| format!("Failed to open {}: {}", archive, e))?;
+        let count = Arc::new(AtomicU64::new(0));
+        let counted = CountingReader {
+            inner: file,
+            count: Arc::clone(&count),
+        };
+        let reader: Box<dyn Read> = if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Box::new(flate2::read::GzDecoder::new(counted))
+        } else if lower.ends_with(".tar.xz") {
+            Box::new(xz2::read::XzDecoder::new(counted))
+        } else if lower.ends_with(".tar.zst") {
+            Box::new(zstd::stream::read::Decoder::new(counted).map_err(|e| e.to_string())?)
+        } else {
+            return Err(format!("Unsupported archive format: {}", archive));
+        };
+
+        let mut tar = tar::Archive::new(reader);
+        for entry in tar.entries().map_err(|e| e.to_string())? {
+            let mut entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+            let target = safe_join(&dest, &path)?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            entry.unpack(&target).map_err(|e| e.to_string())?;
+            emit_progress(&app, count.load(Ordering::Relaxed), compressed_total);
+        }
+    }
+
+    Ok(())
+}
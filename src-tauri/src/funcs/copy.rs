@@ -1,14 +1,91 @@
-use tauri_plugin_shell::{ShellExt, process::CommandEvent};
+use crate::funcs::error::FsError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+use tauri_plugin_shell::{ShellExt, process::CommandEvent};
+
+/// Probe whether the volume backing `dir` folds case, by creating a lowercase
+/// marker file and checking whether the uppercase spelling resolves to it.
+fn probe_case_insensitive(dir: &Path) -> bool {
+    let lower = dir.join(".fastfe-case-probe");
+    let upper = dir.join(".FASTFE-CASE-PROBE");
+    if fs::File::create(&lower).is_err() {
+        // Can't write a probe (e.g. read-only mount): assume the platform default.
+        return cfg!(any(target_os = "macos", target_os = "windows"));
+    }
+    let insensitive = upper.exists();
+    let _ = fs::remove_file(&lower);
+    insensitive
+}
+
+/// Whether the target volume is case-insensitive. Results are cached by the
+/// canonical directory path, so the probe runs once per directory; separate
+/// directories on the same volume are each probed.
+fn volume_case_insensitive(dir: &Path) -> bool {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let key = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+    if let Some(&cached) = cache.lock().unwrap().get(&key) {
+        return cached;
+    }
+    let result = probe_case_insensitive(&key);
+    cache.lock().unwrap().insert(key, result);
+    result
+}
+
+/// Resolve `path` to an absolute, comparable form by canonicalizing its parent
+/// and re-attaching the file name (which may not exist on disk yet).
+fn normalize(path: &Path) -> String {
+    match (path.parent().and_then(|d| d.canonicalize().ok()), path.file_name()) {
+        (Some(dir), Some(name)) => dir.join(name).to_string_lossy().to_string(),
+        _ => path.to_string_lossy().to_string(),
+    }
+}
+
+/// Whether `source` and `destination` resolve to the same underlying file,
+/// accounting for case-folding volumes (where `Report.txt` and `report.txt`
+/// collide). Used to guard copies and moves against self-overwrite.
+fn same_file(source: &str, destination: &str) -> bool {
+    let src = Path::new(source);
+    let dst = Path::new(destination);
+
+    // Both already exist and canonicalize to the same node.
+    if let (Ok(a), Ok(b)) = (src.canonicalize(), dst.canonicalize()) {
+        return a == b;
+    }
+
+    // Destination may not exist yet: compare lexically, folding case when the
+    // target volume does.
+    let target_dir = dst.parent().unwrap_or(dst);
+    let (a, b) = (normalize(src), normalize(dst));
+    if volume_case_insensitive(target_dir) {
+        a.eq_ignore_ascii_case(&b)
+    } else {
+        a == b
+    }
+}
 
 #[tauri::command]
-pub async fn clone(app: tauri::AppHandle, source: &str, destination: &str) -> Result<(), String> {
-    if !fs::metadata(source).is_ok() {
-        return Err(format!("Source path does not exist: {}", source));
+pub async fn clone(app: tauri::AppHandle, source: &str, destination: &str) -> Result<(), FsError> {
+    if fs::metadata(source).is_err() {
+        return Err(FsError::NotFound);
     }
 
-    if source == destination {
-        // return Err("Source and destination paths cannot be the same".to_string());
+    // `rclone copy source destination` treats `destination` as the target
+    // directory, so the file actually written is `destination/<source name>`.
+    // Compare against that path — not the bare directory — or the case-fold
+    // self-copy (e.g. `/d/Report.txt` into `/d`) is never detected.
+    let effective_dest = match Path::new(source).file_name() {
+        Some(name) => Path::new(destination).join(name).to_string_lossy().to_string(),
+        None => destination.to_string(),
+    };
+    if same_file(source, &effective_dest) {
+        // Copying onto itself (including case-only collisions on case-folding
+        // volumes): suffix the destination rather than overwrite the source.
         let new_destination = format!("{} - Copy", destination);
         return Box::pin(clone(app, source, &new_destination)).await;
     }
@@ -16,12 +93,14 @@ pub async fn clone(app: tauri::AppHandle, source: &str, destination: &str) -> Re
     let sidecar_command = app
         .shell()
         .sidecar("rclone")
-        .unwrap()
+        .map_err(|e| FsError::Io(format!("Failed to access rclone sidecar: {}", e)))?
         .args(["copy", source, destination, "-P"]);
 
     println!("Starting rclone transfer from {} to {}", source, destination);
 
-    let (mut rx, _child) = sidecar_command.spawn().map_err(|e| format!("Failed to spawn rclone: {}", e))?;
+    let (mut rx, _child) = sidecar_command
+        .spawn()
+        .map_err(|e| FsError::Io(format!("Failed to spawn rclone: {}", e)))?;
 
     tauri::async_runtime::spawn(async move {
         while let Some(event) = rx.recv().await {
@@ -35,16 +114,171 @@ pub async fn clone(app: tauri::AppHandle, source: &str, destination: &str) -> Re
 }
 
 #[tauri::command]
-pub async fn cut(app: tauri::AppHandle, source: String, destination: String) -> Result<(), String> {
-    fs::rename(&source, &destination)
-        .map_err(|e| format!("Failed to move file: {}", e))?;
-    
-    if cfg!(target_os = "windows") {
-        // On Windows, fs::rename may not work across different drives
-        // Fallback to copy and delete
-        clone(app, &source, &destination).await?;
-        fs::remove_file(&source)
-            .map_err(|e| format!("Failed to delete original file after copy: {}", e))?;
+pub async fn cut(app: tauri::AppHandle, source: String, destination: String) -> Result<(), FsError> {
+    // A move onto the same file (case-only collisions included) would clobber
+    // or no-op; refuse it explicitly.
+    if same_file(&source, &destination) {
+        return Err(FsError::AlreadyExists);
+    }
+
+    match fs::rename(&source, &destination) {
+        Ok(()) => Ok(()),
+        // `fs::rename` can't span volumes; fall back to copy-then-delete only
+        // on a genuine cross-device error rather than guessing by platform.
+        Err(e) => match FsError::from(e) {
+            FsError::CrossDevice => {
+                clone(app, &source, &destination).await?;
+                // The source may be a directory, which `remove_file` can't
+                // delete; fall back to a recursive removal so a cross-device
+                // directory move doesn't orphan the original after a good copy.
+                fs::remove_file(&source).or_else(|_| fs::remove_dir_all(&source))?;
+                Ok(())
+            }
+            other => Err(other),
+        },
     }
+}
+/// Whether a batch item should be copied or moved. Serialized so the frontend
+/// can pick the mode when invoking [`transfer_items`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TransferMode {
+    Copy,
+    Move,
+}
+
+/// Progress for a batch transfer, carrying both the current item's percentage
+/// and the aggregate across the whole queue so the UI can drive two bars.
+#[derive(Debug, Clone, Serialize)]
+struct TransferItemProgress {
+    current_index: usize,
+    total: usize,
+    current_name: String,
+    item_percent: u8,
+    overall_percent: u8,
+}
+
+/// Copy a single item with the rclone sidecar, awaiting completion and
+/// emitting `transfer-progress` as rclone reports percentages.
+async fn copy_item(
+    app: &tauri::AppHandle,
+    source: &str,
+    destination: &str,
+    index: usize,
+    total: usize,
+    name: &str,
+) -> Result<(), String> {
+    let sidecar = app
+        .shell()
+        .sidecar("rclone")
+        .map_err(|e| format!("Failed to access rclone sidecar: {}", e))?
+        .args(["copy", source, destination, "-P"]);
+
+    let (mut rx, _child) = sidecar
+        .spawn()
+        .map_err(|e| format!("Failed to spawn rclone: {}", e))?;
+
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => {
+                let text = String::from_utf8_lossy(&line);
+                // Reuse the shared rclone parser so the batch percentage is
+                // driven by the same schema the frontend gets elsewhere.
+                if let Some(progress) = crate::parse_rclone_progress(&text) {
+                    emit_progress(app, index, total, name, progress.percent);
+                }
+            }
+            CommandEvent::Terminated(payload) => {
+                if payload.code != Some(0) {
+                    return Err(format!("rclone exited with code {:?}", payload.code));
+                }
+                break;
+            }
+            CommandEvent::Error(e) => return Err(format!("rclone process error: {}", e)),
+            _ => {}
+        }
+    }
+
+    emit_progress(app, index, total, name, 100);
+    Ok(())
+}
+
+/// Emit a `transfer-progress` event, folding the current item's percentage into
+/// the overall queue percentage.
+fn emit_progress(
+    app: &tauri::AppHandle,
+    index: usize,
+    total: usize,
+    name: &str,
+    item_percent: u8,
+) {
+    let overall = if total == 0 {
+        0.0
+    } else {
+        ((index as f64 + item_percent as f64 / 100.0) / total as f64) * 100.0
+    };
+
+    let _ = app.emit(
+        "transfer-progress",
+        TransferItemProgress {
+            current_index: index,
+            total,
+            current_name: name.to_string(),
+            item_percent,
+            overall_percent: overall.round() as u8,
+        },
+    );
+}
+
+/// Copy or move a batch of sources into a single destination directory,
+/// processing each item sequentially and emitting `transfer-progress` events.
+///
+/// On `Move`, `fs::rename` is attempted first and only falls back to
+/// copy-then-delete when the rename fails with a cross-device error, so
+/// same-volume moves stay fast while cross-drive moves still succeed.
+#[tauri::command]
+pub async fn transfer_items(
+    app: tauri::AppHandle,
+    sources: Vec<String>,
+    dest: String,
+    mode: TransferMode,
+) -> Result<(), String> {
+    let total = sources.len();
+
+    for (index, source) in sources.iter().enumerate() {
+        if fs::metadata(source).is_err() {
+            return Err(format!("Source path does not exist: {}", source));
+        }
+
+        let name = Path::new(source)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| source.clone());
+        let target = Path::new(&dest).join(&name);
+        let target = target.to_string_lossy().to_string();
+
+        emit_progress(&app, index, total, &name, 0);
+
+        match mode {
+            TransferMode::Copy => {
+                copy_item(&app, source, &dest, index, total, &name).await?;
+            }
+            // `fs::rename` can't span volumes; detect that via the shared
+            // `FsError` mapping rather than a second hand-rolled errno check, so
+            // cross-device handling lives in one place and can't drift.
+            TransferMode::Move => match fs::rename(source, &target) {
+                Ok(()) => emit_progress(&app, index, total, &name, 100),
+                Err(e) => match FsError::from(e) {
+                    FsError::CrossDevice => {
+                        // Different volumes: copy across, then remove the original.
+                        copy_item(&app, source, &dest, index, total, &name).await?;
+                        fs::remove_file(source).or_else(|_| fs::remove_dir_all(source))
+                            .map_err(|e| format!("Failed to remove original after move: {}", e))?;
+                    }
+                    other => return Err(format!("Failed to move {}: {}", source, other)),
+                },
+            },
+        }
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,60 @@
+use serde::Serialize;
+use std::fmt;
+
+/// A machine-readable filesystem error surfaced to the frontend. Serializes
+/// with a `kind` discriminant (and a `message` for [`FsError::Io`]) so the UI
+/// can branch on the variant — e.g. offer a retry on `PermissionDenied` —
+/// instead of matching on opaque error strings.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "message")]
+pub enum FsError {
+    NotFound,
+    NotADirectory,
+    IsDirectory,
+    PermissionDenied,
+    CrossDevice,
+    AlreadyExists,
+    InvalidPath,
+    Io(String),
+}
+
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FsError::NotFound => write!(f, "Path does not exist"),
+            FsError::NotADirectory => write!(f, "Path is not a directory"),
+            FsError::IsDirectory => write!(f, "Path is a directory"),
+            FsError::PermissionDenied => write!(f, "Permission denied"),
+            FsError::CrossDevice => write!(f, "Source and destination are on different devices"),
+            FsError::AlreadyExists => write!(f, "Destination already exists"),
+            FsError::InvalidPath => write!(f, "Invalid path"),
+            FsError::Io(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FsError {}
+
+impl From<std::io::Error> for FsError {
+    fn from(err: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        match err.kind() {
+            ErrorKind::NotFound => FsError::NotFound,
+            ErrorKind::PermissionDenied => FsError::PermissionDenied,
+            ErrorKind::AlreadyExists => FsError::AlreadyExists,
+            // The directory-shape and cross-device kinds aren't stable across
+            // all targets, so fall back to the raw errno for them.
+            _ => match err.raw_os_error() {
+                #[cfg(unix)]
+                Some(18) => FsError::CrossDevice, // EXDEV
+                #[cfg(unix)]
+                Some(20) => FsError::NotADirectory, // ENOTDIR
+                #[cfg(unix)]
+                Some(21) => FsError::IsDirectory, // EISDIR
+                #[cfg(windows)]
+                Some(17) => FsError::CrossDevice, // ERROR_NOT_SAME_DEVICE
+                _ => FsError::Io(err.to_string()),
+            },
+        }
+    }
+}
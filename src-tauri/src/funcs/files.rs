@@ -1,6 +1,12 @@
+use crate::funcs::error::FsError;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, Metadata};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -11,16 +17,51 @@ pub struct FileEntry {
     pub modified: Option<String>,
 }
 
+/// Format a file's modification time the way the UI expects it, or `None`
+/// when the timestamp is unavailable.
+fn format_modified(metadata: &Metadata) -> Option<String> {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| {
+            chrono::DateTime::from_timestamp(d.as_secs() as i64, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default()
+        })
+}
+
+/// Build a `FileEntry` for a single path, reusing the same metadata
+/// extraction as `list_files` so watcher events stay consistent with the
+/// initial listing.
+fn entry_from_path(path: &Path) -> FileEntry {
+    let metadata = fs::metadata(path).ok();
+    let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+    let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+    let modified = metadata.as_ref().and_then(format_modified);
+
+    FileEntry {
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_string_lossy().to_string(),
+        is_dir,
+        size,
+        modified,
+    }
+}
+
 #[tauri::command]
-pub async fn list_files(path: String) -> Result<Vec<FileEntry>, String> {
+pub async fn list_files(path: String) -> Result<Vec<FileEntry>, FsError> {
     let path = Path::new(&path);
 
     if !path.exists() {
-        return Err(format!("Path does not exist: {}", path.display()));
+        return Err(FsError::NotFound);
     }
 
     if !path.is_dir() {
-        return Err(format!("Path is not a directory: {}", path.display()));
+        return Err(FsError::NotADirectory);
     }
 
     let mut entries = Vec::new();
@@ -33,18 +74,7 @@ pub async fn list_files(path: String) -> Result<Vec<FileEntry>, String> {
                         let metadata = entry.metadata().ok();
                         let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
                         let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
-                        let modified = metadata
-                            .and_then(|m| m.modified().ok())
-                            .and_then(|t| {
-                                use std::time::UNIX_EPOCH;
-                                t.duration_since(UNIX_EPOCH).ok()
-                            })
-                            .map(|d| {
-                                let secs = d.as_secs();
-                                chrono::DateTime::from_timestamp(secs as i64, 0)
-                                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
-                                    .unwrap_or_default()
-                            });
+                        let modified = metadata.as_ref().and_then(format_modified);
 
                         entries.push(FileEntry {
                             name: entry.file_name().to_string_lossy().to_string(),
@@ -58,7 +88,7 @@ pub async fn list_files(path: String) -> Result<Vec<FileEntry>, String> {
                 }
             }
         }
-        Err(e) => return Err(format!("Failed to read directory: {}", e)),
+        Err(e) => return Err(FsError::from(e)),
     }
 
     // Sort directories first, then files
@@ -72,66 +102,377 @@ pub async fn list_files(path: String) -> Result<Vec<FileEntry>, String> {
 }
 
 #[tauri::command]
-pub async fn get_home_dir() -> Result<String, String> {
+pub async fn get_home_dir() -> Result<String, FsError> {
     dirs::home_dir()
         .map(|p| p.to_string_lossy().to_string())
-        .ok_or_else(|| "Failed to get home directory".to_string())
+        .ok_or_else(|| FsError::Io("Failed to determine home directory".to_string()))
 }
 
 #[tauri::command]
 pub async fn get_drives() -> Result<Vec<String>, String> {
+    Ok(enumerate_drives())
+}
+
+#[tauri::command]
+pub async fn open_file_in_explorer(path: String) -> Result<(), FsError> {
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg("/select,")
+            .arg(&path)
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open").arg("-R").arg(&path).spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(
+                std::path::Path::new(&path)
+                    .parent()
+                    .unwrap_or(std::path::Path::new("/")),
+            )
+            .spawn()?;
+    }
+
+    Ok(())
+}
+
+/// A single filesystem change reported by [`watch_directory`]. Created and
+/// modified entries carry the same [`FileEntry`] shape as `list_files`;
+/// removals and renames carry bare paths since the target may no longer exist.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum FsChange {
+    Created(FileEntry),
+    Modified(FileEntry),
+    Removed(String),
+    Renamed { from: String, to: String },
+}
+
+/// Live directory watchers keyed by the watched path so multiple tabs viewing
+/// the same directory share a single `notify` watcher instead of duplicating.
+fn watchers() -> &'static Mutex<HashMap<String, notify::RecommendedWatcher>> {
+    static WATCHERS: OnceLock<Mutex<HashMap<String, notify::RecommendedWatcher>>> = OnceLock::new();
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+impl FsChange {
+    /// A stable identity used to coalesce a debounce batch: the variant plus
+    /// the path(s) it touches, so repeated modifies of the same file collapse
+    /// to a single emitted event.
+    fn dedup_key(&self) -> (u8, String) {
+        match self {
+            FsChange::Created(e) => (0, e.path.clone()),
+            FsChange::Modified(e) => (1, e.path.clone()),
+            FsChange::Removed(p) => (2, p.clone()),
+            FsChange::Renamed { from, to } => (3, format!("{}\0{}", from, to)),
+        }
+    }
+}
+
+/// Translate a raw `notify` event into zero or more [`FsChange`]s.
+fn translate(event: notify::Event) -> Vec<FsChange> {
+    use notify::event::{EventKind, ModifyKind, RenameMode};
+
+    match event.kind {
+        EventKind::Create(_) => event
+            .paths
+            .iter()
+            .map(|p| FsChange::Created(entry_from_path(p)))
+            .collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+            vec![FsChange::Renamed {
+                from: event.paths[0].to_string_lossy().to_string(),
+                to: event.paths[1].to_string_lossy().to_string(),
+            }]
+        }
+        // A bare `From`/`To` is a move across the watch boundary reported as a
+        // single side: the source is gone (treat as removed), the destination
+        // is new (treat as created). Letting these fall through to `Modify`
+        // would emit a bogus entry for a path that no longer exists.
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => event
+            .paths
+            .iter()
+            .map(|p| FsChange::Removed(p.to_string_lossy().to_string()))
+            .collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => event
+            .paths
+            .iter()
+            .map(|p| FsChange::Created(entry_from_path(p)))
+            .collect(),
+        EventKind::Modify(_) => event
+            .paths
+            .iter()
+            .map(|p| FsChange::Modified(entry_from_path(p)))
+            .collect(),
+        EventKind::Remove(_) => event
+            .paths
+            .iter()
+            .map(|p| FsChange::Removed(p.to_string_lossy().to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Start watching `path`, emitting debounced `fs-change` events to the
+/// frontend as entries are created, modified, removed, or renamed. Watchers
+/// are keyed by path, so calling this twice for the same directory is a no-op.
+#[tauri::command]
+pub async fn watch_directory(app: AppHandle, path: String) -> Result<(), String> {
+    if watchers().lock().unwrap().contains_key(&path) {
+        return Ok(());
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    use notify::Watcher;
+    watcher
+        .watch(Path::new(&path), notify::RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", path, e))?;
+
+    watchers().lock().unwrap().insert(path.clone(), watcher);
+
+    // Drain raw events on a dedicated thread, coalescing bursts within ~100ms
+    // before emitting so a single save doesn't spam the frontend. The loop ends
+    // when the channel closes, which happens when the watcher is dropped by
+    // `unwatch_directory`.
+    std::thread::spawn(move || {
+        while let Ok(first) = rx.recv() {
+            let mut batch = vec![first];
+            while let Ok(next) = rx.recv_timeout(Duration::from_millis(100)) {
+                batch.push(next);
+            }
+            // Coalesce the burst: keep only the last change per (variant, path)
+            // so a single save producing N modify events emits once.
+            let mut seen = HashSet::new();
+            let mut coalesced = Vec::new();
+            for change in batch
+                .into_iter()
+                .flatten()
+                .flat_map(translate)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+            {
+                if seen.insert(change.dedup_key()) {
+                    coalesced.push(change);
+                }
+            }
+            for change in coalesced.into_iter().rev() {
+                if let Err(e) = app.emit("fs-change", change) {
+                    eprintln!("Failed to emit fs-change: {}", e);
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Tear down the watcher for `path`, if any. Dropping the watcher closes its
+/// event channel, which ends the associated debounce thread.
+#[tauri::command]
+pub async fn unwatch_directory(path: String) -> Result<(), String> {
+    watchers().lock().unwrap().remove(&path);
+    Ok(())
+}
+
+/// Capacity and identity for a mounted drive, emitted by the drive monitor so
+/// the sidebar can render a labelled capacity bar without a manual refresh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriveInfo {
+    pub path: String,
+    pub label: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub is_removable: bool,
+}
+
+/// Currently mounted drive roots. On Windows this is the `GetLogicalDrives`
+/// bitmask; on Linux the device-backed and network mounts in `/proc/mounts`
+/// plus `/`; on macOS the volumes under `/Volumes` plus the root filesystem.
+fn enumerate_drives() -> Vec<String> {
     #[cfg(target_os = "windows")]
     {
         let mut drives = Vec::new();
         let drive_bits = unsafe { windows::Win32::Storage::FileSystem::GetLogicalDrives() };
-
         for i in 0..26 {
             if (drive_bits & (1 << i)) != 0 {
-                let drive_letter = (b'A' + i) as char;
-                drives.push(format!("{}:/", drive_letter));
+                drives.push(format!("{}:/", (b'A' + i) as char));
             }
         }
+        drives
+    }
 
-        Ok(drives)
+    #[cfg(target_os = "linux")]
+    {
+        // Network filesystems whose device field isn't a `/dev/` node
+        // (`host:/export`, `//server/share`); the monitor must surface these so
+        // mounting/unmounting a share shows up in the sidebar.
+        const NETWORK_FSTYPES: &[&str] = &[
+            "nfs", "nfs4", "cifs", "smb3", "smbfs", "afpfs", "ncpfs", "ceph",
+            "glusterfs", "fuse.sshfs",
+        ];
+        let mut drives = Vec::new();
+        if let Ok(mounts) = fs::read_to_string("/proc/mounts") {
+            for line in mounts.lines() {
+                let mut fields = line.split_whitespace();
+                let device = fields.next().unwrap_or_default();
+                let mount_point = fields.next();
+                let fstype = fields.next().unwrap_or_default();
+                if let Some(mount_point) = mount_point {
+                    // Real device-backed mounts plus network shares interest the
+                    // sidebar; skip pseudo/virtual filesystems.
+                    if device.starts_with("/dev/") || NETWORK_FSTYPES.contains(&fstype) {
+                        drives.push(mount_point.to_string());
+                    }
+                }
+            }
+        }
+        if !drives.iter().any(|d| d == "/") {
+            drives.push("/".to_string());
+        }
+        drives
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "macos")]
     {
-        Ok(vec!["/".to_string()])
+        let mut drives = vec!["/".to_string()];
+        if let Ok(entries) = fs::read_dir("/Volumes") {
+            for entry in entries.flatten() {
+                drives.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+        drives
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    {
+        vec!["/".to_string()]
     }
 }
 
-#[tauri::command]
-pub async fn open_file_in_explorer(path: String) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
+/// Total and available bytes for the filesystem backing `path`, or `(0, 0)`
+/// when the capacity cannot be queried.
+fn capacity(path: &str) -> (u64, u64) {
+    #[cfg(unix)]
     {
-        std::process::Command::new("explorer")
-            .arg("/select,")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open explorer: {}", e))?;
+        let c_path = match std::ffi::CString::new(path) {
+            Ok(c) => c,
+            Err(_) => return (0, 0),
+        };
+        unsafe {
+            let mut stat: libc::statvfs = std::mem::zeroed();
+            if libc::statvfs(c_path.as_ptr(), &mut stat) == 0 {
+                let block = stat.f_frsize as u64;
+                return (stat.f_blocks as u64 * block, stat.f_bavail as u64 * block);
+            }
+        }
+        (0, 0)
     }
 
-    #[cfg(target_os = "macos")]
+    #[cfg(windows)]
     {
-        std::process::Command::new("open")
-            .arg("-R")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open finder: {}", e))?;
+        use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+        let wide = windows::core::HSTRING::from(path);
+        let mut free = 0u64;
+        let mut total = 0u64;
+        unsafe {
+            if GetDiskFreeSpaceExW(&wide, Some(&mut free), Some(&mut total), None).is_ok() {
+                return (total, free);
+            }
+        }
+        (0, 0)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = path;
+        (0, 0)
     }
+}
+
+/// Human-readable label for a drive path (drive letter, volume name, or `/`).
+fn drive_label(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.rsplit('/').next().unwrap_or(trimmed).to_string()
+    }
+}
 
+/// Best-effort guess at whether a drive is removable media or a network mount.
+fn is_removable(path: &str) -> bool {
     #[cfg(target_os = "linux")]
     {
-        std::process::Command::new("xdg-open")
-            .arg(
-                std::path::Path::new(&path)
-                    .parent()
-                    .unwrap_or(std::path::Path::new("/")),
-            )
-            .spawn()
-            .map_err(|e| format!("Failed to open file manager: {}", e))?;
+        path.starts_with("/media") || path.starts_with("/run/media") || path.starts_with("/mnt")
+    }
+    #[cfg(target_os = "macos")]
+    {
+        path != "/" && path.starts_with("/Volumes")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Storage::FileSystem::{GetDriveTypeW, DRIVE_REMOTE, DRIVE_REMOVABLE};
+        let wide = windows::core::HSTRING::from(path);
+        let kind = unsafe { GetDriveTypeW(&wide) };
+        kind == DRIVE_REMOVABLE.0 || kind == DRIVE_REMOTE.0
     }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Assemble a [`DriveInfo`] snapshot for a single drive path.
+fn drive_info(path: &str) -> DriveInfo {
+    let (total_bytes, free_bytes) = capacity(path);
+    DriveInfo {
+        path: path.to_string(),
+        label: drive_label(path),
+        total_bytes,
+        free_bytes,
+        is_removable: is_removable(path),
+    }
+}
+
+/// Start a background task that polls mounted drives and emits `drive-added`
+/// and `drive-removed` events as removable media and network mounts appear and
+/// disappear. Idempotent: a second call is a no-op so the sidebar can invoke it
+/// freely on startup.
+#[tauri::command]
+pub async fn start_drive_monitor(app: AppHandle) -> Result<(), String> {
+    static RUNNING: AtomicBool = AtomicBool::new(false);
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    std::thread::spawn(move || {
+        let mut known: HashSet<String> = enumerate_drives().into_iter().collect();
+        loop {
+            std::thread::sleep(Duration::from_secs(2));
+            let current: HashSet<String> = enumerate_drives().into_iter().collect();
+
+            for added in current.difference(&known) {
+                let _ = app.emit("drive-added", drive_info(added));
+            }
+            for removed in known.difference(&current) {
+                let _ = app.emit("drive-removed", drive_info(removed));
+            }
+
+            known = current;
+        }
+    });
 
     Ok(())
 }
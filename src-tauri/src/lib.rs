@@ -1,13 +1,158 @@
+use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_shell::ShellExt;
 
+mod funcs;
+
+use funcs::error::FsError;
+
+/// A single, backend-agnostic progress sample parsed from an rsync or rclone
+/// transfer. Both parsers produce this one schema; rsync samples are emitted
+/// to the frontend on `rsync-output`, while rclone samples only drive the
+/// batch percentage in `transfer-progress` and are not emitted themselves.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferProgress {
+    pub bytes_transferred: u64,
+    pub percent: u8,
+    pub speed_bytes_per_sec: f64,
+    pub eta_seconds: Option<u64>,
+}
+
+/// Split a `"1.23MB/s"`-style token into its numeric and unit halves.
+fn split_num_unit(tok: &str) -> Option<(f64, &str)> {
+    let split = tok
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(tok.len());
+    let (num, unit) = tok.split_at(split);
+    let value = num.replace(',', "").parse::<f64>().ok()?;
+    Some((value, unit))
+}
+
+/// Bytes-per-unit for a size suffix, accepting both SI-ish (`kB`, `MB`) and
+/// binary (`KiB`, `MiB`) spellings; rsync and rclone report 1024-based units.
+fn unit_multiplier(unit: &str) -> f64 {
+    match unit.trim().chars().next().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => 1024.0,
+        Some('m') => 1024f64.powi(2),
+        Some('g') => 1024f64.powi(3),
+        Some('t') => 1024f64.powi(4),
+        _ => 1.0,
+    }
+}
+
+/// Parse a rate token such as `1.23MB/s` into bytes per second.
+fn parse_rate(tok: &str) -> Option<f64> {
+    let (value, unit) = split_num_unit(tok)?;
+    Some(value * unit_multiplier(unit))
+}
+
+/// Parse a `H:MM:SS` or `M:SS` clock (rsync's ETA) into seconds.
+fn parse_eta_clock(tok: &str) -> Option<u64> {
+    let parts: Vec<u64> = tok.split(':').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [h, m, s] => Some(h * 3600 + m * 60 + s),
+        [m, s] => Some(m * 60 + s),
+        _ => None,
+    }
+}
+
+/// Parse a Go-style duration (`1h2m3s`, `45s`) as emitted by rclone's ETA.
+fn parse_eta_go(tok: &str) -> Option<u64> {
+    let mut total = 0u64;
+    let mut num = String::new();
+    for c in tok.chars() {
+        if c.is_ascii_digit() {
+            num.push(c);
+        } else {
+            let value: u64 = num.parse().ok()?;
+            total += match c {
+                'h' => value * 3600,
+                'm' => value * 60,
+                's' => value,
+                _ => return None,
+            };
+            num.clear();
+        }
+    }
+    if num.is_empty() { Some(total) } else { None }
+}
+
+/// Parse an rsync `--info=progress2` line, e.g.
+/// `  1,234,567  45%   1.23MB/s    0:00:12`. Returns `None` for lines that
+/// aren't progress samples so the caller can fall back to the raw line.
+fn parse_rsync_progress(line: &str) -> Option<TransferProgress> {
+    let mut tokens = line.split_whitespace();
+    let bytes_transferred = tokens.next()?.replace(',', "").parse::<u64>().ok()?;
+    let percent = tokens.next()?.strip_suffix('%')?.parse::<u8>().ok()?;
+    let speed_bytes_per_sec = parse_rate(tokens.next()?)?;
+    let eta_seconds = tokens.next().and_then(parse_eta_clock);
+
+    Some(TransferProgress {
+        bytes_transferred,
+        percent,
+        speed_bytes_per_sec,
+        eta_seconds,
+    })
+}
+
+/// Parse an rclone `-P` stats line, e.g.
+/// `Transferred: 1.234 MiB / 5.000 MiB, 25%, 1.234 MiB/s, ETA 3s`. Returns
+/// `None` for the count summary line and anything else that isn't a byte
+/// transfer sample, so callers keep a raw-line fallback.
+pub(crate) fn parse_rclone_progress(line: &str) -> Option<TransferProgress> {
+    if !line.contains('%') || !line.contains('/') {
+        return None;
+    }
+
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    let percent = tokens
+        .iter()
+        .find_map(|t| t.trim_end_matches(',').strip_suffix('%'))
+        .and_then(|n| n.parse::<u8>().ok())?;
+
+    // "<num> <unit> / ..." — the transferred size is the two tokens before `/`.
+    let slash = tokens.iter().position(|&t| t == "/")?;
+    let unit = tokens.get(slash.checked_sub(1)?)?;
+    let num = tokens.get(slash.checked_sub(2)?)?;
+    let bytes_transferred = (num.parse::<f64>().ok()? * unit_multiplier(unit)).round() as u64;
+
+    // Speed may be a single `1.2MiB/s` token or a `1.2` + `MiB/s` pair.
+    let speed_bytes_per_sec = tokens.iter().enumerate().find_map(|(i, t)| {
+        let rate = t.trim_end_matches(',');
+        if !rate.ends_with("/s") {
+            return None;
+        }
+        match split_num_unit(rate) {
+            Some((value, unit)) if value > 0.0 => Some(value * unit_multiplier(unit)),
+            _ => {
+                let num = tokens.get(i.checked_sub(1)?)?.parse::<f64>().ok()?;
+                Some(num * unit_multiplier(rate))
+            }
+        }
+    })?;
+
+    let eta_seconds = tokens
+        .iter()
+        .position(|&t| t == "ETA")
+        .and_then(|i| tokens.get(i + 1))
+        .and_then(|t| parse_eta_go(t.trim_end_matches(',')));
+
+    Some(TransferProgress {
+        bytes_transferred,
+        percent,
+        speed_bytes_per_sec,
+        eta_seconds,
+    })
+}
+
 #[tauri::command]
-async fn transfer_file(app: AppHandle, source: String, dest: String) -> Result<(), String> {
+async fn transfer_file(app: AppHandle, source: String, dest: String) -> Result<(), FsError> {
     // Configure rsync command
     let sidecar = app
         .shell()
         .sidecar("rsync")
-        .map_err(|e| format!("Failed to access rsync sidecar: {}", e))?
+        .map_err(|e| FsError::Io(format!("Failed to access rsync sidecar: {}", e)))?
         .args(["-av", "--info=progress2", &source, &dest]);
 
     println!("Starting rsync from {} to {}", source, dest);
@@ -15,7 +160,7 @@ async fn transfer_file(app: AppHandle, source: String, dest: String) -> Result<(
     // Spawn the process
     let (mut rx, child) = sidecar
         .spawn()
-        .map_err(|e| format!("Failed to spawn rsync: {}", e))?;
+        .map_err(|e| FsError::Io(format!("Failed to spawn rsync: {}", e)))?;
 
     println!("rsync process started with PID: {}", child.pid());
 
@@ -28,7 +173,13 @@ async fn transfer_file(app: AppHandle, source: String, dest: String) -> Result<(
             match event {
                 tauri_plugin_shell::process::CommandEvent::Stdout(line) => {
                     let output = String::from_utf8_lossy(&line).to_string();
-                    if let Err(e) = app.emit("rsync-output", output) {
+                    // Emit structured progress when the line parses; otherwise
+                    // fall back to the raw line so nothing is lost.
+                    let emitted = match parse_rsync_progress(&output) {
+                        Some(progress) => app.emit("rsync-output", progress),
+                        None => app.emit("rsync-output", output),
+                    };
+                    if let Err(e) = emitted {
                         eprintln!("Failed to emit rsync-output: {}", e);
                     }
                 }
@@ -68,7 +219,83 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
-        .invoke_handler(tauri::generate_handler![transfer_file])
+        .invoke_handler(tauri::generate_handler![
+            transfer_file,
+            funcs::files::list_files,
+            funcs::files::get_home_dir,
+            funcs::files::get_drives,
+            funcs::files::open_file_in_explorer,
+            funcs::files::watch_directory,
+            funcs::files::unwatch_directory,
+            funcs::files::start_drive_monitor,
+            funcs::copy::clone,
+            funcs::copy::cut,
+            funcs::copy::transfer_items,
+            funcs::archive::create_archive,
+            funcs::archive::extract_archive,
+        ])
         .run(tauri::generate_context!("tauri.conf.json"))
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_multiplier_handles_size_suffixes() {
+        assert_eq!(unit_multiplier("B/s"), 1.0);
+        assert_eq!(unit_multiplier("kB/s"), 1024.0);
+        assert_eq!(unit_multiplier("MiB/s"), 1024f64.powi(2));
+        assert_eq!(unit_multiplier("GB/s"), 1024f64.powi(3));
+        // Unknown/empty units fall back to a bytes multiplier.
+        assert_eq!(unit_multiplier(""), 1.0);
+    }
+
+    #[test]
+    fn eta_clock_parses_both_shapes() {
+        assert_eq!(parse_eta_clock("0:00:12"), Some(12));
+        assert_eq!(parse_eta_clock("1:02:03"), Some(3723));
+        assert_eq!(parse_eta_clock("2:30"), Some(150));
+        assert_eq!(parse_eta_clock("garbage"), None);
+    }
+
+    #[test]
+    fn eta_go_parses_compound_durations() {
+        assert_eq!(parse_eta_go("3s"), Some(3));
+        assert_eq!(parse_eta_go("1m30s"), Some(90));
+        assert_eq!(parse_eta_go("1h2m3s"), Some(3723));
+        // A trailing bare number with no unit is rejected.
+        assert_eq!(parse_eta_go("12"), None);
+    }
+
+    #[test]
+    fn rsync_progress_strips_separators_and_units() {
+        let p = parse_rsync_progress("  1,234,567  45%   1.23MB/s    0:00:12").unwrap();
+        assert_eq!(p.bytes_transferred, 1_234_567);
+        assert_eq!(p.percent, 45);
+        assert_eq!(p.speed_bytes_per_sec, 1.23 * 1024f64.powi(2));
+        assert_eq!(p.eta_seconds, Some(12));
+    }
+
+    #[test]
+    fn rsync_progress_rejects_non_progress_lines() {
+        assert!(parse_rsync_progress("sending incremental file list").is_none());
+    }
+
+    #[test]
+    fn rclone_progress_parses_transferred_sample() {
+        let p =
+            parse_rclone_progress("Transferred: 1.234 MiB / 5.000 MiB, 25%, 1.234 MiB/s, ETA 3s")
+                .unwrap();
+        assert_eq!(p.percent, 25);
+        assert_eq!(p.bytes_transferred, (1.234 * 1024f64.powi(2)).round() as u64);
+        assert_eq!(p.speed_bytes_per_sec, 1.234 * 1024f64.powi(2));
+        assert_eq!(p.eta_seconds, Some(3));
+    }
+
+    #[test]
+    fn rclone_progress_ignores_summary_lines() {
+        assert!(parse_rclone_progress("Checks: 3 / 3, 100%").is_none());
+    }
+}